@@ -1,18 +1,30 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::{WalkBuilder, WalkState};
 use lazy_static::lazy_static;
+use priority_queue::PriorityQueue;
+use serde::Serialize;
+use std::cmp::Reverse;
 use std::collections::{HashMap as Map, HashSet};
 use std::env;
 use std::fs;
 use std::io;
 use std::process::exit;
-use walkdir::WalkDir;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const DEFAULT_MAX_DEPTH: usize = 2;
 const DEFAULT_CONFIDENCE_THRESHOLD: u32 = 5; // Stop after finding this many files of same type
+const DEFAULT_CACHE_MAX_ENTRIES: usize = 1000;
 const SEARCH_EXTENSIONS_ENV: &'static str = "FIND_EXT_SEARCH_EXTENSIONS";
 const DISALLOWED_FOLDER_ENV: &'static str = "FIND_EXT_DISALLOWED_FOLDERS";
 const CACHE_FILE_ENV: &'static str = "FIND_EXT_CACHE_FILE";
 const USE_CACHE_ENV: &'static str = "FIND_EXT_USE_CACHE";
 const CONFIDENCE_THRESHOLD_ENV: &'static str = "FIND_EXT_CONFIDENCE_THRESHOLD";
+const CACHE_MAX_ENTRIES_ENV: &'static str = "FIND_EXT_CACHE_MAX_ENTRIES";
+const MAX_DEPTH_ENV: &'static str = "FIND_EXT_MAX_DEPTH";
+const RESPECT_GITIGNORE_ENV: &'static str = "FIND_EXT_RESPECT_GITIGNORE";
+const JSON_FLAG: &'static str = "--json";
 
 fn env(key: &str) -> String {
     env::var(key).expect(&format!("Find Ext: set the enviroment variable '{key}'"))
@@ -22,11 +34,38 @@ fn env_as_set(key: &str) -> HashSet<String> {
     env(key).split(',').map(str::to_string).collect()
 }
 
+// Disallowed folders are glob patterns (e.g. `**/node_modules`, `target/`)
+// matched against each entry's path during traversal, not substrings. A
+// pattern with no `/` is a bare folder name (the common case), so it's
+// widened to `**/name` to exclude it at any depth rather than only a path
+// that equals it exactly.
+fn env_as_globset(key: &str) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in env(key).split(',') {
+        let pattern = pattern.trim().trim_end_matches('/');
+        let pattern = if pattern.contains('/') {
+            pattern.to_string()
+        } else {
+            format!("**/{pattern}")
+        };
+        let glob = Glob::new(&pattern).expect("Invalid disallowed-folder glob pattern");
+        builder.add(glob);
+    }
+    builder.build().expect("Invalid disallowed-folder glob pattern")
+}
+
 lazy_static! {
     static ref LOOK_FOR: HashSet<String> = env_as_set(SEARCH_EXTENSIONS_ENV);
-    static ref DISALLOWED_FOLDERS: HashSet<String> = env_as_set(DISALLOWED_FOLDER_ENV);
+    static ref DISALLOWED_FOLDERS: GlobSet = env_as_globset(DISALLOWED_FOLDER_ENV);
     static ref CACHE_FILE: String = env(CACHE_FILE_ENV);
     static ref USE_CACHE: bool = env(USE_CACHE_ENV).parse().unwrap_or(false);
+    // Off by default: the walk keeps counting files that .gitignore/.ignore
+    // exclude, same as before this walker existed. Set to opt into skipping
+    // them like `git`/`rg` would. Hidden files are counted either way.
+    static ref RESPECT_GITIGNORE: bool = env::var(RESPECT_GITIGNORE_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(false);
 }
 
 // Fast marker file detection - check these before walking
@@ -52,35 +91,131 @@ fn check_marker_files(path: &str) -> Option<String> {
     None
 }
 
-#[derive(Debug, Default)]
+// Directory's last-modified time, used to tell whether a cache entry is
+// still valid for the folder it was recorded against. Truncated to whole
+// seconds, since that's all the on-disk cache format persists - comparing
+// a full sub-second-precision mtime against one that was saved and
+// reloaded would never match, since the saved copy lost its nanoseconds.
+fn dir_mtime(path: &str) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .map(|time| UNIX_EPOCH + Duration::from_secs(secs_since_epoch(time)))
+}
+
+fn secs_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn time_from_secs(secs: &str) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs.parse().expect("Invalid Cache"))
+}
+
+// Parses one line of the cache file. The format has grown a field at a time
+// across releases, so older lines are shorter; each older shape is treated
+// as always-stale and least-recently-used rather than rejected, so upgrading
+// find_ext never panics on a cache file written by a previous version.
+fn parse_cache_line(line: &str) -> (String, String, SystemTime, SystemTime) {
+    let fields: Vec<&str> = line.split(';').collect();
+    match fields.as_slice() {
+        [path, extension, mtime, accessed] => (
+            path.to_string(),
+            extension.to_string(),
+            time_from_secs(mtime),
+            time_from_secs(accessed),
+        ),
+        // Entries written between the mtime and LRU releases carry an mtime
+        // but no accessed time.
+        [path, extension, _mtime] => {
+            (path.to_string(), extension.to_string(), UNIX_EPOCH, UNIX_EPOCH)
+        }
+        // Entries from before mtime/LRU tracking predate both fields.
+        [path, extension] => (path.to_string(), extension.to_string(), UNIX_EPOCH, UNIX_EPOCH),
+        _ => panic!("Invalid Cache"),
+    }
+}
+
+// Bounds the cache to `max_entries` folders, evicting the least-recently
+// accessed entry first once that cap is exceeded. This is the LRU eviction
+// strategy librespot-core uses for its own on-disk cache.
+#[derive(Debug)]
+struct SizeLimiter {
+    queue: PriorityQueue<String, Reverse<SystemTime>>,
+    in_use: usize,
+    max_entries: usize,
+}
+
+impl SizeLimiter {
+    fn new(max_entries: usize) -> Self {
+        SizeLimiter {
+            queue: PriorityQueue::new(),
+            in_use: 0,
+            max_entries,
+        }
+    }
+
+    fn touch(&mut self, path: &str, accessed: SystemTime) {
+        if self.queue.change_priority(path, Reverse(accessed)).is_none() {
+            self.queue.push(path.to_string(), Reverse(accessed));
+            self.in_use += 1;
+        }
+    }
+
+    fn evict_over_capacity(&mut self) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.in_use > self.max_entries {
+            match self.queue.pop() {
+                Some((path, _)) => {
+                    self.in_use -= 1;
+                    evicted.push(path);
+                }
+                None => break,
+            }
+        }
+        evicted
+    }
+}
+
+#[derive(Debug)]
 struct Cache {
-    folders: Map<String, String>,
+    folders: Map<String, (String, SystemTime, SystemTime)>,
+    limiter: SizeLimiter,
 }
 
 impl Cache {
-    fn load() -> io::Result<Self> {
+    fn new(max_entries: usize) -> Self {
+        Cache {
+            folders: Map::new(),
+            limiter: SizeLimiter::new(max_entries),
+        }
+    }
+
+    fn load(max_entries: usize) -> io::Result<Self> {
         let raw = fs::read_to_string(CACHE_FILE.to_string())?;
 
-        let mut folders: Map<String, String> = Map::new();
-        let lines = raw.trim().split("\n");
-        for line in lines {
-            let mut pair = line.split(";");
-            let mut next = || pair.next().expect("Invalid Cache").to_string();
-            folders.insert(next(), next());
+        let mut cache = Cache::new(max_entries);
+        for line in raw.trim().split("\n").filter(|line| !line.is_empty()) {
+            let (path, extension, mtime, accessed) = parse_cache_line(line);
+            cache.restore(&path, &extension, mtime, accessed);
         }
+        cache.evict_overflow();
 
-        Ok(Cache { folders })
+        Ok(cache)
     }
 
-    fn load_or_new() -> Self {
-        Self::load().unwrap_or(Self::default())
+    fn load_or_new(max_entries: usize) -> Self {
+        Self::load(max_entries).unwrap_or_else(|_| Self::new(max_entries))
     }
 
     fn save(&self) {
         let raw_data = &self
             .folders
             .iter()
-            .map(|(path, extension)| format!("{path};{extension}"))
+            .map(|(path, (extension, mtime, accessed))| {
+                let mtime = secs_since_epoch(*mtime);
+                let accessed = secs_since_epoch(*accessed);
+                format!("{path};{extension};{mtime};{accessed}")
+            })
             .collect::<Vec<String>>()
             .join("\n");
 
@@ -91,10 +226,49 @@ impl Cache {
         fs::remove_file(&*CACHE_FILE).unwrap();
     }
 
-    fn add(&mut self, path: &str, extension: &str) -> &mut Self {
-        self.folders.insert(path.into(), extension.into());
+    // Restores an entry read from disk without bumping its access time.
+    fn restore(&mut self, path: &str, extension: &str, mtime: SystemTime, accessed: SystemTime) {
+        self.folders
+            .insert(path.to_string(), (extension.to_string(), mtime, accessed));
+        self.limiter.touch(path, accessed);
+    }
+
+    fn add(&mut self, path: &str, extension: &str, mtime: SystemTime) -> &mut Self {
+        let accessed = SystemTime::now();
+        self.folders
+            .insert(path.into(), (extension.into(), mtime, accessed));
+        self.limiter.touch(path, accessed);
+        self.evict_overflow();
         self
     }
+
+    // Bumps the LRU access time for a cache hit without changing its extension/mtime.
+    fn touch(&mut self, path: &str) {
+        let accessed = SystemTime::now();
+        if let Some(entry) = self.folders.get_mut(path) {
+            entry.2 = accessed;
+        }
+        self.limiter.touch(path, accessed);
+        self.evict_overflow();
+    }
+
+    fn evict_overflow(&mut self) {
+        for evicted in self.limiter.evict_over_capacity() {
+            self.folders.remove(&evicted);
+        }
+    }
+}
+
+// What `find_extension` detected for a folder. `Ranked` holds every detected
+// extension with its file count (unordered; `build_report` sorts by count), so
+// callers can render either the top pick or the full breakdown.
+// A cache hit or marker-file match tells us the extension a folder was last
+// (or is now) classified as, but not a real file-count breakdown - so it's
+// kept distinct from a full walk's `Ranked` counts rather than faked as a
+// single-file, 100%-confidence result.
+enum Detection {
+    FastPath(String),
+    Ranked(Map<String, u32>),
 }
 
 fn find_extension(
@@ -103,101 +277,224 @@ fn find_extension(
     look_for: &HashSet<String>,
     cache_opt: &mut Option<Cache>,
     confidence_threshold: u32,
-) -> Option<String> {
+) -> Detection {
+    let current_mtime = dir_mtime(path);
+
     if let Some(cache) = cache_opt {
-        if let Some(ext) = cache.folders.get(path) {
-            return Some(ext.to_string());
+        if let Some((ext, cached_mtime, _)) = cache.folders.get(path) {
+            if current_mtime.is_some_and(|mtime| mtime == *cached_mtime) {
+                let ext = ext.to_string();
+                cache.touch(path);
+                return Detection::FastPath(ext);
+            }
         }
     }
 
     if let Some(marker_ext) = check_marker_files(path) {
         if look_for.contains(&marker_ext) {
             if let Some(cache) = cache_opt {
-                cache.add(path, &marker_ext);
+                cache.add(path, &marker_ext, current_mtime.unwrap_or(UNIX_EPOCH));
             }
-            return Some(marker_ext);
+            return Detection::FastPath(marker_ext);
         }
     }
 
-    let mut counts: Map<String, u32> = Map::new();
-    let mut max_count = 0u32;
-    let mut leading_ext: Option<String> = None;
-
-    for entry in WalkDir::new(&path)
-        .max_depth(depth)
-        .into_iter()
-        .filter_entry(|e| {
-            !DISALLOWED_FOLDERS
-                .iter()
-                .any(|disallowed| e.path().to_string_lossy().contains(disallowed))
-        })
-        .filter_map(|e| e.ok())
-    {
-        if let Some(ext) = entry.path().extension().and_then(|ext| ext.to_str()) {
-            if look_for.contains(ext) {
-                let count = counts.entry(ext.to_string()).or_insert(0);
-                *count += 1;
-
-                if *count > max_count {
-                    max_count = *count;
-                    leading_ext = Some(ext.to_string());
+    // Shared across worker threads so any one of them crossing the
+    // confidence threshold tells the others to stop traversing too.
+    let shared_max_count = AtomicU32::new(0);
+    let counts: Mutex<Map<String, u32>> = Mutex::new(Map::new());
+
+    // `build_parallel` walks subdirectories concurrently across a thread
+    // pool instead of collecting the whole tree into a `Vec` first, so the
+    // traversal itself - the dominant cost at higher depths - is what gets
+    // parallelized, not just the in-memory tally afterward. Disallowed
+    // folders are pruned via filter_entry instead of walking into them and
+    // rejecting the result afterward. `WalkBuilder` defaults to skipping
+    // hidden files and honoring .gitignore, neither of which the old
+    // `WalkDir`-based scan did: hidden files are always counted, and ignore
+    // files are only honored when FIND_EXT_RESPECT_GITIGNORE is set.
+    WalkBuilder::new(path)
+        .max_depth(Some(depth))
+        .hidden(false)
+        .ignore(*RESPECT_GITIGNORE)
+        .git_ignore(*RESPECT_GITIGNORE)
+        .git_global(*RESPECT_GITIGNORE)
+        .git_exclude(*RESPECT_GITIGNORE)
+        .parents(*RESPECT_GITIGNORE)
+        .filter_entry(|entry| !DISALLOWED_FOLDERS.is_match(entry.path()))
+        .build_parallel()
+        .run(|| {
+            Box::new(|entry_result| {
+                if shared_max_count.load(Ordering::Relaxed) >= confidence_threshold {
+                    return WalkState::Quit;
                 }
 
-                // Early exit: if we found enough files of one type, stop searching
-                if max_count >= confidence_threshold {
-                    break;
+                if let Ok(entry) = entry_result {
+                    if let Some(ext) = entry.path().extension().and_then(|ext| ext.to_str()) {
+                        if look_for.contains(ext) {
+                            let mut counts = counts.lock().unwrap();
+                            let count = counts.entry(ext.to_string()).or_insert(0);
+                            *count += 1;
+                            shared_max_count.fetch_max(*count, Ordering::Relaxed);
+                        }
+                    }
                 }
-            }
-        }
-    }
 
-    let max_ext = if max_count >= confidence_threshold {
-        leading_ext
-    } else {
-        counts
-            .into_iter()
-            .max_by_key(|(_, count)| *count)
-            .map(|(ext, _)| ext)
-    };
+                WalkState::Continue
+            })
+        });
+
+    let counts = counts.into_inner().unwrap();
+
+    let max_ext = counts.iter().max_by_key(|(_, count)| *count).map(|(ext, _)| ext.clone());
 
     if let Some(ext) = &max_ext {
         if let Some(cache) = cache_opt {
-            cache.add(path, ext);
+            cache.add(path, ext, current_mtime.unwrap_or(UNIX_EPOCH));
         }
     }
 
-    max_ext
+    Detection::Ranked(counts)
+}
+
+#[derive(Serialize)]
+struct ExtensionMatch {
+    extension: String,
+    // `None` when this match came from a cache hit or marker file - a real
+    // breakdown was never computed, so there's nothing honest to report here.
+    count: Option<u32>,
+    confidence: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct PathReport {
+    path: String,
+    // Whether `matches` reflects a real file-count breakdown (a full walk)
+    // or a single fast-path guess with no count/confidence behind it.
+    ranked: bool,
+    matches: Vec<ExtensionMatch>,
+}
+
+// Ranks the detected extensions by file count and attaches each one's share
+// of the total as a confidence percentage. A fast-path detection (cache hit
+// or marker file) short-circuits to a single match with no count/confidence,
+// since no breakdown was computed for it.
+fn build_report(path: &str, detection: Detection) -> PathReport {
+    let (ranked, matches) = match detection {
+        Detection::FastPath(extension) => (
+            false,
+            vec![ExtensionMatch {
+                extension,
+                count: None,
+                confidence: None,
+            }],
+        ),
+        Detection::Ranked(counts) => {
+            let total: u32 = counts.values().sum();
+
+            let mut matches: Vec<ExtensionMatch> = counts
+                .into_iter()
+                .map(|(extension, count)| {
+                    let confidence = if total > 0 {
+                        (count as f64 / total as f64) * 100.0
+                    } else {
+                        0.0
+                    };
+                    ExtensionMatch {
+                        extension,
+                        count: Some(count),
+                        confidence: Some(confidence),
+                    }
+                })
+                .collect();
+            matches.sort_by_key(|m| Reverse(m.count));
+
+            (true, matches)
+        }
+    };
+
+    PathReport {
+        path: path.to_string(),
+        ranked,
+        matches,
+    }
+}
+
+// Expands a positional argument as a glob (e.g. `src/*`). Literal paths are
+// passed through as-is even if the glob crate finds no match for them, so a
+// plain missing path still reaches `find_extension` the way it used to.
+// "Literal" covers both paths with no glob metacharacters and paths that
+// happen to contain one (e.g. `weird[dir]`) but exist on disk regardless -
+// only a pattern that resolves to nothing AND isn't itself a real path is
+// treated as an actual glob that simply matched nothing.
+fn expand_paths(pattern: &str) -> Vec<String> {
+    let matched: Vec<String> = match glob::glob(pattern) {
+        Ok(paths) => paths
+            .filter_map(|p| p.ok())
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let is_literal_path = !pattern.contains(['*', '?', '[']) || fs::metadata(pattern).is_ok();
+    if matched.is_empty() && is_literal_path {
+        vec![pattern.to_string()]
+    } else {
+        matched
+    }
 }
 
 fn display_help_message() {
     let msg = r#"
-Usage: find_ext PATH
-    --clear (-c) = Clear cache 
+Usage: find_ext PATH... [--json]
+       find_ext PATH DEPTH (legacy: DEPTH only applies with a single PATH)
+    --clear (-c) = Clear cache
+    --json       = Print the full ranked extension breakdown as JSON
     "#
     .trim();
     println!("{msg}");
 }
 
 fn main() {
-    let mut cache = (*USE_CACHE).then_some(Cache::load_or_new());
+    let cache_max_entries = env::var(CACHE_MAX_ENTRIES_ENV)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_CACHE_MAX_ENTRIES);
+
+    let mut cache = (*USE_CACHE).then_some(Cache::load_or_new(cache_max_entries));
 
-    let args: Vec<String> = env::args().collect();
-    let path = match args.get(1) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first() {
         Some(p) if p == "-c" || p == "--clear" => {
             Cache::clear();
             println!("Cleared cache!");
             return;
         }
-        Some(p) => p,
+        Some(_) => {}
         None => {
             display_help_message();
             exit(1);
         }
+    }
+
+    let json_output = args.iter().any(|arg| arg == JSON_FLAG);
+    let patterns: Vec<&String> = args.iter().filter(|arg| *arg != JSON_FLAG).collect();
+
+    // Legacy CLI shape: `find_ext PATH DEPTH`. A single path followed by a
+    // bare number is a depth override, not a second path, so it's peeled
+    // off here rather than expanded as a glob.
+    let (patterns, legacy_depth): (Vec<&String>, Option<usize>) = match patterns.as_slice() {
+        [p, d] if d.parse::<usize>().is_ok() => (vec![*p], d.parse::<usize>().ok()),
+        _ => (patterns, None),
     };
 
-    let depth = args
-        .get(2)
-        .and_then(|t| t.parse::<usize>().ok())
+    let paths: Vec<String> = patterns
+        .into_iter()
+        .flat_map(|pattern| expand_paths(pattern))
+        .collect();
+
+    let depth = legacy_depth
+        .or_else(|| env::var(MAX_DEPTH_ENV).ok().and_then(|v| v.parse::<usize>().ok()))
         .unwrap_or(DEFAULT_MAX_DEPTH);
 
     let confidence_threshold = env::var(CONFIDENCE_THRESHOLD_ENV)
@@ -205,12 +502,118 @@ fn main() {
         .and_then(|v| v.parse::<u32>().ok())
         .unwrap_or(DEFAULT_CONFIDENCE_THRESHOLD);
 
-    let output = find_extension(path, depth, &*LOOK_FOR, &mut cache, confidence_threshold);
+    let reports: Vec<PathReport> = paths
+        .iter()
+        .map(|path| {
+            let detection = find_extension(path, depth, &LOOK_FOR, &mut cache, confidence_threshold);
+            build_report(path, detection)
+        })
+        .collect();
 
     // Save cache once at the end if we have one
     if let Some(c) = cache {
         c.save();
     }
 
-    println!("{}", output.unwrap_or_default());
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+    } else {
+        for report in &reports {
+            let top = report.matches.first().map(|m| m.extension.as_str());
+            println!("{}", top.unwrap_or_default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cache_line_four_fields_keeps_mtime_and_accessed() {
+        let (path, extension, mtime, accessed) = parse_cache_line("/a;rs;100;200");
+        assert_eq!(path, "/a");
+        assert_eq!(extension, "rs");
+        assert_eq!(mtime, UNIX_EPOCH + Duration::from_secs(100));
+        assert_eq!(accessed, UNIX_EPOCH + Duration::from_secs(200));
+    }
+
+    #[test]
+    fn parse_cache_line_three_fields_is_treated_as_stale() {
+        let (path, extension, mtime, accessed) = parse_cache_line("/a;rs;100");
+        assert_eq!(path, "/a");
+        assert_eq!(extension, "rs");
+        assert_eq!(mtime, UNIX_EPOCH);
+        assert_eq!(accessed, UNIX_EPOCH);
+    }
+
+    #[test]
+    fn parse_cache_line_two_fields_is_treated_as_stale() {
+        let (path, extension, mtime, accessed) = parse_cache_line("/a;rs");
+        assert_eq!(path, "/a");
+        assert_eq!(extension, "rs");
+        assert_eq!(mtime, UNIX_EPOCH);
+        assert_eq!(accessed, UNIX_EPOCH);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Cache")]
+    fn parse_cache_line_rejects_malformed_lines() {
+        parse_cache_line("/a;rs;100;200;300");
+    }
+
+    // Regression test: a real path whose name happens to contain glob
+    // metacharacters (e.g. `[dir]`) used to be silently dropped when
+    // `glob::glob` found no match for it, instead of falling back to the
+    // literal path like any other non-glob-matching argument.
+    #[test]
+    fn expand_paths_falls_back_to_literal_path_with_glob_metachars() {
+        let dir = std::env::temp_dir().join("find_ext_test_weird[dir]");
+        fs::create_dir_all(&dir).expect("create test dir");
+
+        let pattern = dir.to_str().expect("utf8 path").to_string();
+        assert_eq!(expand_paths(&pattern), vec![pattern.clone()]);
+
+        fs::remove_dir_all(&dir).expect("clean up test dir");
+    }
+
+    // Regression test: `dir_mtime` used to return full sub-second precision
+    // while the cache file only ever persisted whole seconds, so a saved
+    // and reloaded mtime could never compare equal to a fresh `dir_mtime`
+    // call - defeating the cache on every run after the first.
+    #[test]
+    fn cached_mtime_survives_save_load_round_trip() {
+        let mtime = dir_mtime(".").expect("current dir must have a mtime");
+
+        let line = format!("/a;rs;{};{}", secs_since_epoch(mtime), secs_since_epoch(mtime));
+        let (_, _, loaded_mtime, _) = parse_cache_line(&line);
+
+        assert_eq!(loaded_mtime, mtime);
+    }
+
+    #[test]
+    fn evict_over_capacity_is_noop_under_the_limit() {
+        let mut limiter = SizeLimiter::new(2);
+        limiter.touch("/a", UNIX_EPOCH);
+        assert!(limiter.evict_over_capacity().is_empty());
+    }
+
+    #[test]
+    fn evict_over_capacity_evicts_least_recently_touched_first() {
+        let mut limiter = SizeLimiter::new(2);
+        limiter.touch("/oldest", UNIX_EPOCH);
+        limiter.touch("/middle", UNIX_EPOCH + Duration::from_secs(1));
+        limiter.touch("/newest", UNIX_EPOCH + Duration::from_secs(2));
+
+        assert_eq!(limiter.evict_over_capacity(), vec!["/oldest".to_string()]);
+        assert!(limiter.evict_over_capacity().is_empty());
+    }
+
+    #[test]
+    fn touching_an_existing_path_updates_its_priority_instead_of_growing() {
+        let mut limiter = SizeLimiter::new(1);
+        limiter.touch("/a", UNIX_EPOCH);
+        limiter.touch("/a", UNIX_EPOCH + Duration::from_secs(1));
+        assert!(limiter.evict_over_capacity().is_empty());
+    }
 }